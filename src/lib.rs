@@ -1,10 +1,24 @@
 extern crate wasm_bindgen;
 extern crate wee_alloc;
 extern crate schnorrkel;
-
+extern crate merlin;
+extern crate bs58;
+extern crate blake2;
+extern crate zeroize;
+extern crate js_sys;
+
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fmt;
+
+use blake2::{Blake2b512, Digest, Blake2b, digest::consts::U32};
+use merlin::Transcript;
+use zeroize::{Zeroize, Zeroizing};
 use schnorrkel::{
-	Keypair, MiniSecretKey, PublicKey, SecretKey, Signature,
+	ExpansionMode, Keypair, MiniSecretKey, PublicKey, SecretKey, Signature, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH,
 	derive::{Derivation, ChainCode, CHAIN_CODE_LENGTH},
+	musig::{self, AggregatePublicKey},
+	signing_context,
 };
 use wasm_bindgen::prelude::*;
 
@@ -15,6 +29,80 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 // We must make sure that this is the same as declared in the substrate source code.
 const SIGNING_CTX: &'static [u8] = b"substrate";
 
+// Prefixed to the payload before hashing, per the SS58 address format spec.
+const SS58_PREFIX: &'static [u8] = b"SS58PRE";
+
+// Number of checksum bytes appended to an SS58 address, taken from the front
+// of the blake2b-512 digest of the prefixed payload.
+const SS58_CHECKSUM_LENGTH: usize = 2;
+
+// 256-bit flavour of blake2b, used to fold over-long derivation junctions
+// down to a single chain code.
+type Blake2b256 = Blake2b<U32>;
+
+/// Errors that can be returned from the wasm entry points in place of panicking.
+///
+/// These are surfaced to JS as catchable exceptions via `JsValue`.
+#[derive(Debug)]
+pub enum SchnorrkelError {
+	InvalidSeed,
+	InvalidKeypair,
+	InvalidPublicKey,
+	InvalidSecretKey,
+	InvalidSignature,
+	InvalidCommitment,
+	InvalidReveal,
+	InvalidCosignature,
+	MuSigStageMismatch,
+	InvalidSs58Address,
+	InvalidSs58Checksum,
+	InvalidDerivationPath,
+	BatchLengthMismatch,
+	MuSigLengthMismatch,
+}
+
+impl fmt::Display for SchnorrkelError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SchnorrkelError::InvalidSeed => write!(f, "Provided seed is invalid."),
+			SchnorrkelError::InvalidKeypair => write!(f, "Provided pair is invalid."),
+			SchnorrkelError::InvalidPublicKey => write!(f, "Provided public key is invalid."),
+			SchnorrkelError::InvalidSecretKey => write!(f, "Provided private key is invalid."),
+			SchnorrkelError::InvalidSignature => write!(f, "Provided signature is invalid."),
+			SchnorrkelError::InvalidCommitment => write!(f, "Provided MuSig commitment is invalid."),
+			SchnorrkelError::InvalidReveal => write!(f, "Provided MuSig reveal is invalid."),
+			SchnorrkelError::InvalidCosignature => write!(f, "Provided MuSig cosignature is invalid."),
+			SchnorrkelError::MuSigStageMismatch => write!(f, "MuSig session is not in the expected round."),
+			SchnorrkelError::InvalidSs58Address => write!(f, "Provided SS58 address is invalid."),
+			SchnorrkelError::InvalidSs58Checksum => write!(f, "SS58 address checksum does not match."),
+			SchnorrkelError::InvalidDerivationPath => write!(f, "Provided derivation path is invalid."),
+			SchnorrkelError::BatchLengthMismatch => write!(f, "Signatures, messages and public keys must have the same length."),
+			SchnorrkelError::MuSigLengthMismatch => write!(f, "Public keys, reveals and cosignatures must have the same length."),
+		}
+	}
+}
+
+/// Compute the 2-byte SS58 checksum for a network-prefixed public key.
+fn ss58_checksum(prefixed: &[u8]) -> [u8; SS58_CHECKSUM_LENGTH] {
+	let mut hasher = Blake2b512::new();
+
+	hasher.update(SS58_PREFIX);
+	hasher.update(prefixed);
+
+	let digest = hasher.finalize();
+	let mut checksum = [0u8; SS58_CHECKSUM_LENGTH];
+
+	checksum.copy_from_slice(&digest[0..SS58_CHECKSUM_LENGTH]);
+
+	checksum
+}
+
+impl From<SchnorrkelError> for JsValue {
+	fn from(error: SchnorrkelError) -> JsValue {
+		JsValue::from_str(&error.to_string())
+	}
+}
+
 /// ChainCode construction helper
 fn create_cc(data: &[u8]) -> ChainCode {
 	let mut cc = [0u8; CHAIN_CODE_LENGTH];
@@ -25,35 +113,26 @@ fn create_cc(data: &[u8]) -> ChainCode {
 }
 
 /// Keypair helper function.
-fn create_from_seed(seed: &[u8]) -> Keypair {
-	match MiniSecretKey::from_bytes(seed) {
-		Ok(mini) => return mini.expand_to_keypair(),
-		Err(_) => panic!("Provided seed is invalid.")
-	}
+fn create_from_seed(seed: &[u8]) -> Result<Keypair, SchnorrkelError> {
+	MiniSecretKey::from_bytes(seed)
+		.map(Zeroizing::new)
+		.map(|mini| mini.expand_to_keypair(ExpansionMode::Ed25519))
+		.map_err(|_| SchnorrkelError::InvalidSeed)
 }
 
 /// Keypair helper function.
-fn create_from_pair(pair: &[u8]) -> Keypair {
-	match Keypair::from_bytes(pair) {
-		Ok(pair) => return pair,
-		Err(_) => panic!("Provided pair is invalid.")
-	}
+fn create_from_pair(pair: &[u8]) -> Result<Keypair, SchnorrkelError> {
+	Keypair::from_bytes(pair).map_err(|_| SchnorrkelError::InvalidKeypair)
 }
 
 /// PublicKey helper
-fn create_public(public: &[u8]) -> PublicKey {
-	match PublicKey::from_bytes(public) {
-		Ok(public) => return public,
-		Err(_) => panic!("Provided public key is invalid.")
-	}
+fn create_public(public: &[u8]) -> Result<PublicKey, SchnorrkelError> {
+	PublicKey::from_bytes(public).map_err(|_| SchnorrkelError::InvalidPublicKey)
 }
 
 /// SecretKey helper
-fn create_secret(secret: &[u8]) -> SecretKey {
-	match SecretKey::from_bytes(secret) {
-		Ok(secret) => return secret,
-		Err(_) => panic!("Provided private key is invalid.")
-	}
+fn create_secret(secret: &[u8]) -> Result<SecretKey, SchnorrkelError> {
+	SecretKey::from_bytes(secret).map_err(|_| SchnorrkelError::InvalidSecretKey)
 }
 
 /// Perform a derivation on a secret
@@ -63,12 +142,22 @@ fn create_secret(secret: &[u8]) -> SecretKey {
 ///
 /// returned vector the derived keypair as a array of 96 bytes
 #[wasm_bindgen]
-pub fn derive_keypair_hard(pair: &[u8], cc: &[u8]) -> Vec<u8> {
-	create_from_pair(pair).secret
-		.hard_derive_mini_secret_key(Some(create_cc(cc)), &[]).0
-		.expand_to_keypair()
+pub fn derive_keypair_hard(pair: &[u8], cc: &[u8]) -> Result<Vec<u8>, JsValue> {
+	Ok(derive_keypair_hard_impl(pair, cc)?)
+}
+
+/// Core of `derive_keypair_hard`, returning a plain `SchnorrkelError` so it
+/// can be exercised without going through `JsValue`.
+fn derive_keypair_hard_impl(pair: &[u8], cc: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+	let keypair = Zeroizing::new(create_from_pair(pair)?);
+	let mini = Zeroizing::new(keypair.secret
+		.hard_derive_mini_secret_key(Some(create_cc(cc)), &[]).0);
+	let derived = mini
+		.expand_to_keypair(ExpansionMode::Ed25519)
 		.to_bytes()
-		.to_vec()
+		.to_vec();
+
+	Ok(derived)
 }
 
 /// Perform a derivation on a secret
@@ -78,11 +167,20 @@ pub fn derive_keypair_hard(pair: &[u8], cc: &[u8]) -> Vec<u8> {
 ///
 /// returned vector the derived keypair as a array of 96 bytes
 #[wasm_bindgen]
-pub fn derive_keypair_soft(pair: &[u8], cc: &[u8]) -> Vec<u8> {
-	create_from_pair(pair)
+pub fn derive_keypair_soft(pair: &[u8], cc: &[u8]) -> Result<Vec<u8>, JsValue> {
+	Ok(derive_keypair_soft_impl(pair, cc)?)
+}
+
+/// Core of `derive_keypair_soft`, returning a plain `SchnorrkelError` so it
+/// can be exercised without going through `JsValue`.
+fn derive_keypair_soft_impl(pair: &[u8], cc: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+	let keypair = Zeroizing::new(create_from_pair(pair)?);
+	let derived = keypair
 		.derived_key_simple(create_cc(cc), &[]).0
 		.to_bytes()
-		.to_vec()
+		.to_vec();
+
+	Ok(derived)
 }
 
 /// Perform a derivation on a publicKey
@@ -92,10 +190,125 @@ pub fn derive_keypair_soft(pair: &[u8], cc: &[u8]) -> Vec<u8> {
 ///
 /// returned vector is the derived publicKey as a array of 32 bytes
 #[wasm_bindgen]
-pub fn derive_public_soft(public: &[u8], cc: &[u8]) -> Vec<u8> {
-	create_public(public)
+pub fn derive_public_soft(public: &[u8], cc: &[u8]) -> Result<Vec<u8>, JsValue> {
+	Ok(derive_public_soft_impl(public, cc)?)
+}
+
+/// Core of `derive_public_soft`, returning a plain `SchnorrkelError` so it
+/// can be exercised without going through `JsValue`.
+fn derive_public_soft_impl(public: &[u8], cc: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+	let public = create_public(public)?
 		.derived_key_simple(create_cc(cc), &[]).0
-		.to_bytes().to_vec()
+		.to_bytes().to_vec();
+
+	Ok(public)
+}
+
+/// SCALE-encode a junction token as a compact-length-prefixed byte string,
+/// matching how substrate encodes a non-numeric `DeriveJunction`.
+fn scale_compact_string(token: &str) -> Vec<u8> {
+	let bytes = token.as_bytes();
+	let len = bytes.len();
+	let mut encoded = Vec::with_capacity(len + 4);
+
+	if len < 64 {
+		encoded.push((len as u8) << 2);
+	} else if len < (1 << 14) {
+		encoded.extend_from_slice(&(((len as u16) << 2) | 0b01).to_le_bytes());
+	} else if len < (1 << 30) {
+		encoded.extend_from_slice(&(((len as u32) << 2) | 0b10).to_le_bytes());
+	} else {
+		encoded.push(0b11);
+		encoded.extend_from_slice(&(len as u32).to_le_bytes());
+	}
+
+	encoded.extend_from_slice(bytes);
+	encoded
+}
+
+/// Chain code for a single junction token: its SCALE encoding (or its little-endian
+/// bytes, if the token is a plain `u64`), zero-padded or blake2b-256-hashed to 32 bytes.
+fn junction_chain_code(token: &str) -> ChainCode {
+	let encoded = match token.parse::<u64>() {
+		Ok(index) => index.to_le_bytes().to_vec(),
+		Err(_) => scale_compact_string(token),
+	};
+
+	if encoded.len() <= CHAIN_CODE_LENGTH {
+		let mut cc = [0u8; CHAIN_CODE_LENGTH];
+		cc[..encoded.len()].copy_from_slice(&encoded);
+		ChainCode(cc)
+	} else {
+		let mut hasher = Blake2b256::new();
+		hasher.update(&encoded);
+		create_cc(&hasher.finalize())
+	}
+}
+
+/// Parse a substrate-style derivation path into its junctions, in order.
+///
+/// `//` introduces a hard junction, `/` a soft junction; returns `(hard, token)`
+/// pairs, e.g. `//Alice/foo//1` parses to `[(true, "Alice"), (false, "foo"), (true, "1")]`.
+fn parse_junctions(path: &str) -> Result<Vec<(bool, String)>, SchnorrkelError> {
+	let mut junctions = Vec::new();
+	let mut remaining = path;
+
+	while !remaining.is_empty() {
+		let hard = remaining.starts_with("//");
+		remaining = if hard {
+			&remaining[2..]
+		} else if remaining.starts_with('/') {
+			&remaining[1..]
+		} else {
+			return Err(SchnorrkelError::InvalidDerivationPath);
+		};
+
+		let end = remaining.find('/').unwrap_or(remaining.len());
+		let (token, rest) = remaining.split_at(end);
+
+		if token.is_empty() {
+			return Err(SchnorrkelError::InvalidDerivationPath);
+		}
+
+		junctions.push((hard, token.to_string()));
+		remaining = rest;
+	}
+
+	Ok(junctions)
+}
+
+/// Derive a key pair by walking every junction of a substrate-style path in one call.
+///
+/// * pair: UIntArray with 96 element
+/// * path: a junction path such as `//Alice/foo//1`, where `//` is a hard
+///   junction and `/` is a soft junction
+///
+/// returned vector the derived keypair as a array of 96 bytes
+#[wasm_bindgen]
+pub fn derive_keypair_path(pair: &[u8], path: &str) -> Result<Vec<u8>, JsValue> {
+	Ok(derive_keypair_path_impl(pair, path)?)
+}
+
+/// Core of `derive_keypair_path`, returning a plain `SchnorrkelError` so it
+/// can be exercised without going through `JsValue`.
+fn derive_keypair_path_impl(pair: &[u8], path: &str) -> Result<Vec<u8>, SchnorrkelError> {
+	let mut keypair = Zeroizing::new(create_from_pair(pair)?);
+
+	for (hard, token) in parse_junctions(path)? {
+		let cc = junction_chain_code(&token);
+
+		*keypair = if hard {
+			let mini = Zeroizing::new(keypair.secret
+				.hard_derive_mini_secret_key(Some(cc), &[])
+				.0);
+
+			mini.expand_to_keypair(ExpansionMode::Ed25519)
+		} else {
+			keypair.derived_key_simple(cc, &[]).0
+		};
+	}
+
+	Ok(keypair.to_bytes().to_vec())
 }
 
 /// Generate a key pair.
@@ -105,10 +318,18 @@ pub fn derive_public_soft(public: &[u8], cc: &[u8]) -> Vec<u8> {
 /// returned vector is the concatenation of first the private key (64 bytes)
 /// followed by the public key (32) bytes.
 #[wasm_bindgen]
-pub fn keypair_from_seed(seed: &[u8]) -> Vec<u8> {
-	create_from_seed(seed)
+pub fn keypair_from_seed(seed: &[u8]) -> Result<Vec<u8>, JsValue> {
+	Ok(keypair_from_seed_impl(seed)?)
+}
+
+/// Core of `keypair_from_seed`, returning a plain `SchnorrkelError` so it
+/// can be exercised without going through `JsValue`.
+fn keypair_from_seed_impl(seed: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+	let keypair = Zeroizing::new(create_from_seed(seed)?)
 		.to_bytes()
-		.to_vec()
+		.to_vec();
+
+	Ok(keypair)
 }
 
 /// Sign a message
@@ -122,11 +343,30 @@ pub fn keypair_from_seed(seed: &[u8]) -> Vec<u8> {
 ///
 /// * returned vector is the signature consisting of 64 bytes.
 #[wasm_bindgen]
-pub fn sign(public: &[u8], secret: &[u8], message: &[u8]) -> Vec<u8> {
-	create_secret(secret)
-		.sign_simple(SIGNING_CTX, message, &create_public(public))
+pub fn sign(public: &[u8], secret: &[u8], message: &[u8]) -> Result<Vec<u8>, JsValue> {
+	Ok(sign_impl(public, secret, message)?)
+}
+
+/// Core of `sign`, returning a plain `SchnorrkelError` so it can be
+/// exercised without going through `JsValue`.
+fn sign_impl(public: &[u8], secret: &[u8], message: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+	let secret = Zeroizing::new(create_secret(secret)?);
+	let signature = secret
+		.sign_simple(SIGNING_CTX, message, &create_public(public)?)
 		.to_bytes()
-		.to_vec()
+		.to_vec();
+
+	Ok(signature)
+}
+
+/// Overwrite a buffer with zeroes.
+///
+/// Call this from JS once a secret byte array (e.g. the keypair or secret key
+/// bytes returned by this module) has been copied out and is no longer needed,
+/// so it doesn't linger readable in wasm linear memory.
+#[wasm_bindgen]
+pub fn zeroize_buffer(buf: &mut [u8]) {
+	buf.zeroize();
 }
 
 /// Verify a message and its corresponding against a public key;
@@ -134,15 +374,332 @@ pub fn sign(public: &[u8], secret: &[u8], message: &[u8]) -> Vec<u8> {
 /// * signature: UIntArray with 64 element
 /// * message: Arbitrary length UIntArray
 /// * pubkey: UIntArray with 32 element
+///
+/// Returns an error if the signature or public key cannot be parsed, rather
+/// than conflating a malformed signature with one that simply fails to verify.
 #[wasm_bindgen]
-pub fn verify(signature: &[u8], message: &[u8], public: &[u8]) -> bool {
-	let signature = match Signature::from_bytes(signature) {
-		Ok(signature) => signature,
-		Err(_) => return false
-	};
+pub fn verify(signature: &[u8], message: &[u8], public: &[u8]) -> Result<bool, JsValue> {
+	Ok(verify_impl(signature, message, public)?)
+}
+
+/// Core of `verify`, returning a plain `SchnorrkelError` so it can be
+/// exercised without going through `JsValue`.
+fn verify_impl(signature: &[u8], message: &[u8], public: &[u8]) -> Result<bool, SchnorrkelError> {
+	let signature = Signature::from_bytes(signature)
+		.map_err(|_| SchnorrkelError::InvalidSignature)?;
+
+	Ok(create_public(public)?.verify_simple(SIGNING_CTX, message, &signature).is_ok())
+}
+
+/// Verify a batch of signatures in a single pass, far cheaper per-signature
+/// than calling `verify` in a loop since the expensive group operations are
+/// amortized across the whole batch.
+///
+/// * signatures: flat buffer of 64-byte signatures
+/// * messages: one entry per signature, in the same order
+/// * publics: flat buffer of 32-byte public keys, in the same order
+///
+/// Returns `true` only if every signature in the batch is valid; a single
+/// corrupted signature fails the whole batch.
+#[wasm_bindgen]
+pub fn verify_batch(signatures: &[u8], messages: Vec<js_sys::Uint8Array>, publics: &[u8]) -> Result<bool, JsValue> {
+	let messages: Vec<Vec<u8>> = messages.iter().map(|message| message.to_vec()).collect();
+
+	Ok(verify_batch_impl(signatures, &messages, publics)?)
+}
+
+/// Core of `verify_batch`, taking plain owned messages so it can be exercised
+/// without going through `js_sys::Uint8Array`.
+fn verify_batch_impl(signatures: &[u8], messages: &[Vec<u8>], publics: &[u8]) -> Result<bool, SchnorrkelError> {
+	let signatures = split_exact(signatures, SIGNATURE_LENGTH, SchnorrkelError::InvalidSignature)?
+		.map(|bytes| Signature::from_bytes(bytes).map_err(|_| SchnorrkelError::InvalidSignature))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let publics = split_exact(publics, PUBLIC_KEY_LENGTH, SchnorrkelError::InvalidPublicKey)?
+		.map(|bytes| PublicKey::from_bytes(bytes).map_err(|_| SchnorrkelError::InvalidPublicKey))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	if messages.len() != signatures.len() || messages.len() != publics.len() {
+		return Err(SchnorrkelError::BatchLengthMismatch);
+	}
+
+	let transcripts = messages.iter().map(|message| signing_context(SIGNING_CTX).bytes(message));
+
+	Ok(schnorrkel::verify_batch(transcripts, &signatures, &publics, false).is_ok())
+}
+
+/// Encode a public key as a SS58 address for the given network.
+///
+/// * public: UIntArray with 32 element
+/// * network_prefix: the SS58 network identifier, e.g. 42 for the default substrate network
+#[wasm_bindgen]
+pub fn public_to_ss58(public: &[u8], network_prefix: u8) -> Result<String, JsValue> {
+	Ok(public_to_ss58_impl(public, network_prefix)?)
+}
+
+/// Core of `public_to_ss58`, returning a plain `SchnorrkelError` so it can
+/// be exercised without going through `JsValue`.
+fn public_to_ss58_impl(public: &[u8], network_prefix: u8) -> Result<String, SchnorrkelError> {
+	create_public(public)?;
+
+	let mut prefixed = Vec::with_capacity(1 + PUBLIC_KEY_LENGTH + SS58_CHECKSUM_LENGTH);
+
+	prefixed.push(network_prefix);
+	prefixed.extend_from_slice(public);
+	prefixed.extend_from_slice(&ss58_checksum(&prefixed));
+
+	Ok(bs58::encode(prefixed).into_string())
+}
+
+/// Decode a SS58 address back into its raw 32-byte public key.
+///
+/// Returns an error if the address isn't valid base58, or if its checksum
+/// doesn't match the network prefix and public key it carries.
+#[wasm_bindgen]
+pub fn ss58_to_public(address: &str) -> Result<Vec<u8>, JsValue> {
+	Ok(ss58_to_public_impl(address)?)
+}
+
+/// Core of `ss58_to_public`, returning a plain `SchnorrkelError` so it can
+/// be exercised without going through `JsValue`.
+fn ss58_to_public_impl(address: &str) -> Result<Vec<u8>, SchnorrkelError> {
+	let decoded = bs58::decode(address).into_vec()
+		.map_err(|_| SchnorrkelError::InvalidSs58Address)?;
+
+	if decoded.len() != 1 + PUBLIC_KEY_LENGTH + SS58_CHECKSUM_LENGTH {
+		return Err(SchnorrkelError::InvalidSs58Address);
+	}
+
+	let (prefixed, checksum) = decoded.split_at(1 + PUBLIC_KEY_LENGTH);
+
+	if &ss58_checksum(prefixed)[..] != checksum {
+		return Err(SchnorrkelError::InvalidSs58Checksum);
+	}
+
+	Ok(prefixed[1..].to_vec())
+}
+
+const MUSIG_COMMITMENT_LENGTH: usize = 16;
+const MUSIG_REVEAL_LENGTH: usize = 128;
+const MUSIG_COSIGNATURE_LENGTH: usize = 32;
+
+/// Split a flat buffer into fixed-size chunks, rejecting buffers whose length
+/// isn't an exact multiple of `size`.
+fn split_exact<'a>(data: &'a [u8], size: usize, err: SchnorrkelError) -> Result<std::slice::Chunks<'a, u8>, SchnorrkelError> {
+	if size == 0 || data.len() % size != 0 {
+		return Err(err);
+	}
+
+	Ok(data.chunks(size))
+}
+
+/// Copy a chunk of known length into a fixed-size array, for the MuSig types
+/// that store their bytes as a plain `[u8; N]` rather than offering a
+/// `from_bytes` constructor.
+fn array_exact<const N: usize>(chunk: &[u8], err: SchnorrkelError) -> Result<[u8; N], SchnorrkelError> {
+	chunk.try_into().map_err(|_| err)
+}
+
+/// The round a `MuSigSession` is currently in.
+///
+/// Held in an `Option` on `MuSigSession` so each round transition can
+/// consume the previous round's state, matching the schnorrkel `musig` API.
+enum MuSigRound {
+	Commit(musig::MuSig<Transcript, musig::CommitStage<Keypair>>),
+	Reveal(musig::MuSig<Transcript, musig::RevealStage<Keypair>>),
+	/// Nothing left to compute once cosigned: finalizing a signature is the
+	/// standalone `musig_finalize`'s job, so this round only needs to mark
+	/// that the session has moved past `musig_cosign`.
+	Cosign,
+}
+
+/// An in-progress MuSig co-signing session for a single participant.
+///
+/// The protocol runs in rounds (commit, reveal, cosign) so the state must be
+/// threaded through an opaque handle rather than returned as plain bytes.
+#[wasm_bindgen]
+pub struct MuSigSession {
+	round: Option<MuSigRound>,
+}
+
+#[wasm_bindgen]
+impl MuSigSession {
+	/// This signer's commitment for the current round, to be broadcast to the
+	/// other co-signers.
+	pub fn musig_commit(&self) -> Result<Vec<u8>, JsValue> {
+		Ok(self.musig_commit_impl()?)
+	}
 
-	create_public(public)
-		.verify_simple(SIGNING_CTX, message, &signature)
+	/// Move from the commit round to the reveal round once every other
+	/// co-signer's commitment has been collected.
+	///
+	/// * publics: flat buffer of 32-byte public keys, one per other co-signer (excluding this session's own)
+	/// * commitments: flat buffer of 16-byte commitments, in the same order as `publics`
+	pub fn musig_reveal(&mut self, publics: &[u8], commitments: &[u8]) -> Result<Vec<u8>, JsValue> {
+		Ok(self.musig_reveal_impl(publics, commitments)?)
+	}
+
+	/// Move from the reveal round to the cosign round once every other
+	/// co-signer's reveal has been collected.
+	///
+	/// * publics: flat buffer of 32-byte public keys, one per other co-signer (excluding this session's own)
+	/// * reveals: flat buffer of 128-byte reveals, in the same order as `publics`
+	pub fn musig_cosign(&mut self, publics: &[u8], reveals: &[u8]) -> Result<Vec<u8>, JsValue> {
+		Ok(self.musig_cosign_impl(publics, reveals)?)
+	}
+
+}
+
+impl MuSigSession {
+	/// Core of `musig_commit`, returning a plain `SchnorrkelError` so it can
+	/// be exercised without going through `JsValue`.
+	fn musig_commit_impl(&self) -> Result<Vec<u8>, SchnorrkelError> {
+		match &self.round {
+			Some(MuSigRound::Commit(musig)) => Ok(musig.our_commitment().0.to_vec()),
+			_ => Err(SchnorrkelError::MuSigStageMismatch),
+		}
+	}
+
+	/// Core of `musig_reveal`, returning a plain `SchnorrkelError` so it can
+	/// be exercised without going through `JsValue`.
+	fn musig_reveal_impl(&mut self, publics: &[u8], commitments: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+		let mut musig = match self.round.take() {
+			Some(MuSigRound::Commit(musig)) => musig,
+			_ => return Err(SchnorrkelError::MuSigStageMismatch),
+		};
+
+		let publics = split_exact(publics, PUBLIC_KEY_LENGTH, SchnorrkelError::InvalidPublicKey)?;
+		let commitments = split_exact(commitments, MUSIG_COMMITMENT_LENGTH, SchnorrkelError::InvalidCommitment)?;
+
+		for (public, commitment) in publics.zip(commitments) {
+			let public = PublicKey::from_bytes(public).map_err(|_| SchnorrkelError::InvalidPublicKey)?;
+			let commitment = musig::Commitment(array_exact(commitment, SchnorrkelError::InvalidCommitment)?);
+
+			musig.add_their_commitment(public, commitment).map_err(|_| SchnorrkelError::InvalidCommitment)?;
+		}
+
+		let musig = musig.reveal_stage();
+		let our_reveal = musig.our_reveal().0.to_vec();
+
+		self.round = Some(MuSigRound::Reveal(musig));
+
+		Ok(our_reveal)
+	}
+
+	/// Core of `musig_cosign`, returning a plain `SchnorrkelError` so it can
+	/// be exercised without going through `JsValue`.
+	fn musig_cosign_impl(&mut self, publics: &[u8], reveals: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+		let mut musig = match self.round.take() {
+			Some(MuSigRound::Reveal(musig)) => musig,
+			_ => return Err(SchnorrkelError::MuSigStageMismatch),
+		};
+
+		let publics = split_exact(publics, PUBLIC_KEY_LENGTH, SchnorrkelError::InvalidPublicKey)?;
+		let reveals = split_exact(reveals, MUSIG_REVEAL_LENGTH, SchnorrkelError::InvalidReveal)?;
+
+		for (public, reveal) in publics.zip(reveals) {
+			let public = PublicKey::from_bytes(public).map_err(|_| SchnorrkelError::InvalidPublicKey)?;
+			let reveal = musig::Reveal(array_exact(reveal, SchnorrkelError::InvalidReveal)?);
+
+			musig.add_their_reveal(public, reveal).map_err(|_| SchnorrkelError::InvalidReveal)?;
+		}
+
+		let musig = musig.cosign_stage();
+		let our_cosignature = musig.our_cosignature().0.to_vec();
+
+		self.round = Some(MuSigRound::Cosign);
+
+		Ok(our_cosignature)
+	}
+
+}
+
+/// Start a MuSig co-signing session for `message` using `pair`.
+///
+/// * pair: UIntArray with 96 element, as returned by `keypair_from_seed`
+/// * message: Arbitrary length UIntArray
+#[wasm_bindgen]
+pub fn musig_new_signer(pair: &[u8], message: &[u8]) -> Result<MuSigSession, JsValue> {
+	Ok(musig_new_signer_impl(pair, message)?)
+}
+
+/// Core of `musig_new_signer`, returning a plain `SchnorrkelError` so it can
+/// be exercised without going through `JsValue`.
+///
+/// Unlike the other functions here, this one doesn't wrap `keypair` in
+/// `Zeroizing`: `musig::MuSig::new<K: Borrow<Keypair>>` takes `K` by value,
+/// and `Zeroizing<Keypair>` has no `Borrow<Keypair>` impl, so it can't be
+/// passed through directly. That's fine, not just a compromise — `Keypair`
+/// zeroizes its own secret on `Drop` (see `schnorrkel::keys::Keypair`), so
+/// the `MuSigSession` holding it here is zeroized automatically once
+/// dropped, same as if we'd wrapped it ourselves.
+fn musig_new_signer_impl(pair: &[u8], message: &[u8]) -> Result<MuSigSession, SchnorrkelError> {
+	let keypair = create_from_pair(pair)?;
+	let transcript = signing_context(SIGNING_CTX).bytes(message);
+	let musig = musig::MuSig::new(keypair, transcript);
+
+	Ok(MuSigSession {
+		round: Some(MuSigRound::Commit(musig)),
+	})
+}
+
+/// Combine every co-signer's reveal and cosignature, produced by
+/// `MuSigSession::musig_reveal` and `MuSigSession::musig_cosign`, into the
+/// single 64-byte signature that `verify` accepts against the aggregated
+/// public key from `aggregate_public_keys`.
+///
+/// * message: the same message the session was started with
+/// * publics: flat buffer of 32-byte public keys, one per co-signer
+/// * reveals: flat buffer of 128-byte reveals, one per co-signer, in the same order as `publics`
+/// * cosignatures: flat buffer of 32-byte cosignatures, one per co-signer, in the same order as `publics`
+#[wasm_bindgen]
+pub fn musig_finalize(message: &[u8], publics: &[u8], reveals: &[u8], cosignatures: &[u8]) -> Result<Vec<u8>, JsValue> {
+	Ok(musig_finalize_impl(message, publics, reveals, cosignatures)?)
+}
+
+/// Core of `musig_finalize`, returning a plain `SchnorrkelError` so it can
+/// be exercised without going through `JsValue`.
+fn musig_finalize_impl(message: &[u8], publics: &[u8], reveals: &[u8], cosignatures: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+	let publics = split_exact(publics, PUBLIC_KEY_LENGTH, SchnorrkelError::InvalidPublicKey)?
+		.map(|bytes| PublicKey::from_bytes(bytes).map_err(|_| SchnorrkelError::InvalidPublicKey))
+		.collect::<Result<Vec<_>, _>>()?;
+	let reveals = split_exact(reveals, MUSIG_REVEAL_LENGTH, SchnorrkelError::InvalidReveal)?
+		.map(|bytes| array_exact(bytes, SchnorrkelError::InvalidReveal).map(musig::Reveal))
+		.collect::<Result<Vec<_>, _>>()?;
+	let cosignatures = split_exact(cosignatures, MUSIG_COSIGNATURE_LENGTH, SchnorrkelError::InvalidCosignature)?
+		.map(|bytes| array_exact(bytes, SchnorrkelError::InvalidCosignature).map(musig::Cosignature))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	if publics.len() != reveals.len() || publics.len() != cosignatures.len() {
+		return Err(SchnorrkelError::MuSigLengthMismatch);
+	}
+
+	let mut collector = musig::collect_cosignatures(signing_context(SIGNING_CTX).bytes(message));
+
+	for ((public, reveal), cosignature) in publics.into_iter().zip(reveals).zip(cosignatures) {
+		collector.add(public, reveal, cosignature).map_err(|_| SchnorrkelError::InvalidCosignature)?;
+	}
+
+	Ok(collector.signature().to_bytes().to_vec())
+}
+
+/// Reconstruct the joint public key for a set of co-signers, so a verifier can
+/// check a finalized MuSig signature with the existing `verify` export.
+///
+/// * publics: flat buffer of 32-byte public keys, one per co-signer
+#[wasm_bindgen]
+pub fn aggregate_public_keys(publics: &[u8]) -> Result<Vec<u8>, JsValue> {
+	Ok(aggregate_public_keys_impl(publics)?)
+}
+
+/// Core of `aggregate_public_keys`, returning a plain `SchnorrkelError` so it
+/// can be exercised without going through `JsValue`.
+fn aggregate_public_keys_impl(publics: &[u8]) -> Result<Vec<u8>, SchnorrkelError> {
+	let publics: BTreeMap<PublicKey, ()> = split_exact(publics, PUBLIC_KEY_LENGTH, SchnorrkelError::InvalidPublicKey)?
+		.map(|bytes| PublicKey::from_bytes(bytes).map(|public| (public, ())).map_err(|_| SchnorrkelError::InvalidPublicKey))
+		.collect::<Result<_, _>>()?;
+
+	Ok(publics.public_key().to_bytes().to_vec())
 }
 
 #[cfg(test)]
@@ -150,7 +707,7 @@ pub mod tests {
 	extern crate rand;
 	extern crate schnorrkel;
 
-	use hex_literal::{hex, hex_impl};
+	use hex_literal::hex;
 	use super::*;
 	use schnorrkel::{SIGNATURE_LENGTH, KEYPAIR_LENGTH, SECRET_KEY_LENGTH};
 
@@ -161,7 +718,7 @@ pub mod tests {
 	#[test]
 	fn can_create_keypair() {
 		let seed = generate_random_seed();
-		let keypair = keypair_from_seed(seed.as_slice());
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
 
 		assert!(keypair.len() == KEYPAIR_LENGTH);
 	}
@@ -170,20 +727,27 @@ pub mod tests {
 	fn creates_pair_from_known() {
 		let seed = hex!("fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e");
 		let expected = hex!("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a");
-		let keypair = keypair_from_seed(&seed);
+		let keypair = keypair_from_seed(&seed).unwrap();
 		let public = &keypair[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
 
 		assert_eq!(public, expected);
 	}
 
+	#[test]
+	fn rejects_invalid_seed() {
+		let seed = vec![0u8; 4];
+
+		assert!(keypair_from_seed_impl(&seed).is_err());
+	}
+
 	#[test]
 	fn can_sign_message() {
 		let seed = generate_random_seed();
-		let keypair = keypair_from_seed(seed.as_slice());
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
 		let private = &keypair[0..SECRET_KEY_LENGTH];
 		let public = &keypair[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
 		let message = b"this is a message";
-		let signature = sign(public, private, message);
+		let signature = sign(public, private, message).unwrap();
 
 		assert!(signature.len() == SIGNATURE_LENGTH);
 	}
@@ -191,13 +755,24 @@ pub mod tests {
 	#[test]
 	fn can_verify_message() {
 		let seed = generate_random_seed();
-		let keypair = keypair_from_seed(seed.as_slice());
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
 		let private = &keypair[0..SECRET_KEY_LENGTH];
 		let public = &keypair[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
 		let message = b"this is a message";
-		let signature = sign(public, private, message);
+		let signature = sign(public, private, message).unwrap();
+
+		assert!(verify(&signature[..], message, public).unwrap());
+	}
+
+	#[test]
+	fn rejects_unparseable_signature() {
+		let seed = generate_random_seed();
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
+		let public = &keypair[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
+		let message = b"this is a message";
+		let bad_signature = vec![0u8; SIGNATURE_LENGTH];
 
-		assert!(verify(&signature[..], message, public));
+		assert!(verify_impl(&bad_signature, message, public).is_err());
 	}
 
 	#[test]
@@ -205,8 +780,8 @@ pub mod tests {
 		let cc = hex!("0c666f6f00000000000000000000000000000000000000000000000000000000"); // foo
 		let seed = hex!("fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e");
 		let expected = hex!("40b9675df90efa6069ff623b0fdfcf706cd47ca7452a5056c7ad58194d23440a");
-		let keypair = keypair_from_seed(&seed);
-		let derived = derive_keypair_soft(&keypair, &cc);
+		let keypair = keypair_from_seed(&seed).unwrap();
+		let derived = derive_keypair_soft(&keypair, &cc).unwrap();
 		let public = &derived[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
 
 		assert_eq!(public, expected);
@@ -218,7 +793,7 @@ pub mod tests {
 		let public = hex!("46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a");
 		let expected = hex!("40b9675df90efa6069ff623b0fdfcf706cd47ca7452a5056c7ad58194d23440a");
 
-		assert_eq!(derive_public_soft(&public, &cc), expected);
+		assert_eq!(derive_public_soft(&public, &cc).unwrap(), expected);
 	}
 
 	#[test]
@@ -226,10 +801,159 @@ pub mod tests {
 		let cc = hex!("14416c6963650000000000000000000000000000000000000000000000000000"); // Alice
 		let seed = hex!("fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e");
 		let expected = hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
-		let keypair = keypair_from_seed(&seed);
-		let derived = derive_keypair_hard(&keypair, &cc);
+		let keypair = keypair_from_seed(&seed).unwrap();
+		let derived = derive_keypair_hard(&keypair, &cc).unwrap();
 		let public = &derived[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
 
 		assert_eq!(public, expected);
 	}
+
+	#[test]
+	fn path_derives_same_as_hard_derive() {
+		let seed = hex!("fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e");
+		let expected = hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+		let keypair = keypair_from_seed(&seed).unwrap();
+		let derived = derive_keypair_path(&keypair, "//Alice").unwrap();
+		let public = &derived[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
+
+		assert_eq!(public, expected);
+	}
+
+	#[test]
+	fn path_derives_same_as_soft_derive() {
+		let seed = hex!("fac7959dbfe72f052e5a0c3c8d6530f202b02fd8f9f5ca3580ec8deb7797479e");
+		let expected = hex!("40b9675df90efa6069ff623b0fdfcf706cd47ca7452a5056c7ad58194d23440a");
+		let keypair = keypair_from_seed(&seed).unwrap();
+		let derived = derive_keypair_path(&keypair, "/foo").unwrap();
+		let public = &derived[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
+
+		assert_eq!(public, expected);
+	}
+
+	#[test]
+	fn rejects_malformed_path() {
+		let seed = generate_random_seed();
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
+
+		assert!(derive_keypair_path_impl(&keypair, "Alice").is_err());
+	}
+
+	#[test]
+	fn musig_new_signer_keypair_implements_zeroize() {
+		// `musig_new_signer_impl` intentionally moves a bare `Keypair` into
+		// `musig::MuSig::new` rather than wrapping it in `Zeroizing`, relying on
+		// `Keypair` zeroizing its own secret on `Drop` instead (see
+		// `schnorrkel::keys::Keypair`). This compile-time assertion pins the
+		// `Zeroize` impl so a future change that swaps in a non-zeroizing key
+		// type doesn't silently drop the guarantee.
+		fn assert_impls_zeroize<T: Zeroize>() {}
+
+		assert_impls_zeroize::<Keypair>();
+	}
+
+	fn signed_fixture(message: &[u8]) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+		let seed = generate_random_seed();
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
+		let private = keypair[0..SECRET_KEY_LENGTH].to_vec();
+		let public = keypair[SECRET_KEY_LENGTH..KEYPAIR_LENGTH].to_vec();
+		let signature = sign(&public, &private, message).unwrap();
+
+		(signature, public, message.to_vec())
+	}
+
+	#[test]
+	fn verifies_a_batch_of_signatures() {
+		let fixtures = vec![
+			signed_fixture(b"first message"),
+			signed_fixture(b"second message"),
+			signed_fixture(b"third message"),
+		];
+
+		let signatures: Vec<u8> = fixtures.iter().flat_map(|(sig, _, _)| sig.clone()).collect();
+		let publics: Vec<u8> = fixtures.iter().flat_map(|(_, public, _)| public.clone()).collect();
+		let messages: Vec<Vec<u8>> = fixtures.iter().map(|(_, _, message)| message.clone()).collect();
+
+		assert!(verify_batch_impl(&signatures, &messages, &publics).unwrap());
+	}
+
+	#[test]
+	fn a_single_corrupted_signature_fails_the_whole_batch() {
+		let fixtures = vec![
+			signed_fixture(b"first message"),
+			signed_fixture(b"second message"),
+			signed_fixture(b"third message"),
+		];
+
+		let mut signatures: Vec<u8> = fixtures.iter().flat_map(|(sig, _, _)| sig.clone()).collect();
+		signatures[0] ^= 0xff;
+
+		let publics: Vec<u8> = fixtures.iter().flat_map(|(_, public, _)| public.clone()).collect();
+		let messages: Vec<Vec<u8>> = fixtures.iter().map(|(_, _, message)| message.clone()).collect();
+
+		assert!(!verify_batch_impl(&signatures, &messages, &publics).unwrap());
+	}
+
+	#[test]
+	fn ss58_round_trips_a_public_key() {
+		let seed = generate_random_seed();
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
+		let public = &keypair[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
+
+		let address = public_to_ss58(public, 42).unwrap();
+		let decoded = ss58_to_public(&address).unwrap();
+
+		assert_eq!(decoded, public);
+	}
+
+	#[test]
+	fn rejects_ss58_address_with_corrupted_checksum() {
+		let seed = generate_random_seed();
+		let keypair = keypair_from_seed(seed.as_slice()).unwrap();
+		let public = &keypair[SECRET_KEY_LENGTH..KEYPAIR_LENGTH];
+
+		let mut decoded = bs58::decode(public_to_ss58(public, 42).unwrap()).into_vec().unwrap();
+		*decoded.last_mut().unwrap() ^= 0xff;
+		let corrupted = bs58::encode(decoded).into_string();
+
+		assert!(ss58_to_public_impl(&corrupted).is_err());
+	}
+
+	#[test]
+	fn musig_two_of_two_round_trip_verifies_against_aggregate_public_keys() {
+		let message = b"two-of-two musig message";
+		let pair_a = keypair_from_seed(&generate_random_seed()).unwrap();
+		let pair_b = keypair_from_seed(&generate_random_seed()).unwrap();
+		let public_a = pair_a[SECRET_KEY_LENGTH..KEYPAIR_LENGTH].to_vec();
+		let public_b = pair_b[SECRET_KEY_LENGTH..KEYPAIR_LENGTH].to_vec();
+
+		let mut session_a = musig_new_signer(&pair_a, message).unwrap();
+		let mut session_b = musig_new_signer(&pair_b, message).unwrap();
+
+		let commit_a = session_a.musig_commit().unwrap();
+		let commit_b = session_b.musig_commit().unwrap();
+
+		let reveal_a = session_a.musig_reveal(&public_b, &commit_b).unwrap();
+		let reveal_b = session_b.musig_reveal(&public_a, &commit_a).unwrap();
+
+		let cosign_a = session_a.musig_cosign(&public_b, &reveal_b).unwrap();
+		let cosign_b = session_b.musig_cosign(&public_a, &reveal_a).unwrap();
+
+		let publics: Vec<u8> = [public_a, public_b].concat();
+		let reveals: Vec<u8> = [reveal_a, reveal_b].concat();
+		let cosignatures: Vec<u8> = [cosign_a, cosign_b].concat();
+
+		let signature = musig_finalize(message, &publics, &reveals, &cosignatures).unwrap();
+		let aggregated_public = aggregate_public_keys(&publics).unwrap();
+
+		assert!(verify(&signature, message, &aggregated_public).unwrap());
+	}
+
+	#[test]
+	fn zeroize_buffer_clears_its_contents() {
+		let mut keypair = keypair_from_seed(&generate_random_seed()).unwrap();
+
+		zeroize_buffer(&mut keypair);
+
+		assert!(keypair.iter().all(|byte| *byte == 0));
+	}
 }